@@ -0,0 +1,138 @@
+//! Pluggable offload backends for precompile-accelerated Keccak (e.g. inside a zkVM guest).
+//!
+//! [`Keccak`](super::Keccak) used to hardcode a 100_000-byte buffering cutoff
+//! and a direct call into `risc0_zkvm`'s `keccak_digest` precompile: inputs
+//! that stayed under the cutoff were buffered whole and handed to the
+//! precompile at `finalize`, while anything bigger fell back to the regular
+//! incremental sponge. That baked one vendor's accelerator and cutoff into
+//! the hasher, so this trait factors both decisions out into something
+//! callers can implement for their own zkVM/hardware precompile, with the
+//! cutoff itself exposed as a constructor parameter.
+#[cfg(target_os = "zkvm")]
+extern crate alloc;
+#[cfg(target_os = "zkvm")]
+use alloc::vec::Vec;
+
+/// A backend that can compute a Keccak digest faster than absorbing bytes one
+/// round at a time, e.g. a zkVM guest precompile or hardware accelerator.
+pub trait KeccakAccelerator: Clone {
+    /// Returns `true` once `buffered_len` total bytes have been seen and the
+    /// hasher should give up on offloading and fall back to the incremental
+    /// sponge for the rest of this message. The default simply compares
+    /// against `threshold`; override it for a backend with a non-linear
+    /// cost model.
+    fn should_fall_back(&self, buffered_len: usize, threshold: usize) -> bool {
+        buffered_len > threshold
+    }
+
+    /// Computes the digest of `raw_data` padded with the one-byte
+    /// domain-separation `delim`, writing it into `output`.
+    ///
+    /// Returns `false` if the backend could not service this call (e.g. the
+    /// precompile rejected the input), in which case the caller falls back
+    /// to the incremental sponge.
+    fn accelerated_digest(&self, raw_data: &[u8], delim: u8, output: &mut [u8]) -> bool;
+}
+
+/// The buffering/offload cutoff that used to be hardcoded to 100_000 bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OffloadThreshold(pub usize);
+
+impl Default for OffloadThreshold {
+    fn default() -> Self {
+        OffloadThreshold(100_000)
+    }
+}
+
+/// Buffers input below [`OffloadThreshold`] so it can be handed whole to an
+/// accelerator at `finalize`, falling back to the incremental sponge once the
+/// threshold is crossed.
+#[cfg(target_os = "zkvm")]
+#[derive(Clone)]
+pub(crate) struct AcceleratedBuffer<A> {
+    accelerator: A,
+    threshold: OffloadThreshold,
+    raw_data: Vec<u8>,
+    fell_back: bool,
+}
+
+#[cfg(target_os = "zkvm")]
+impl<A: KeccakAccelerator> AcceleratedBuffer<A> {
+    pub(crate) fn new(accelerator: A, threshold: OffloadThreshold) -> Self {
+        AcceleratedBuffer {
+            accelerator,
+            threshold,
+            raw_data: Vec::new(),
+            fell_back: false,
+        }
+    }
+
+    /// Returns `Some(data)` once the incremental sponge needs to absorb
+    /// `data` directly (either because this call crossed the threshold, in
+    /// which case `data` is the whole backlog plus `input`, or because a
+    /// prior call already fell back).
+    pub(crate) fn absorb<'a>(&'a mut self, input: &'a [u8]) -> Option<&'a [u8]> {
+        if self.fell_back {
+            return Some(input);
+        }
+        let would_be_len = self.raw_data.len() + input.len();
+        self.raw_data.extend_from_slice(input);
+        if self.accelerator.should_fall_back(would_be_len, self.threshold.0) {
+            self.fell_back = true;
+            return Some(&self.raw_data);
+        }
+        None
+    }
+
+    pub(crate) fn fell_back(&self) -> bool {
+        self.fell_back
+    }
+
+    pub(crate) fn raw_data(&self) -> &[u8] {
+        &self.raw_data
+    }
+
+    pub(crate) fn finalize(&self, delim: u8, output: &mut [u8]) -> bool {
+        self.accelerator.accelerated_digest(&self.raw_data, delim, output)
+    }
+}
+
+/// An accelerator that never offloads; [`Keccak`](super::Keccak) falls back
+/// to the incremental sponge on the very first byte. This is the default
+/// backend when no precompile-specific feature (e.g. `risc0`) is enabled, so
+/// `target_os = "zkvm"` builds still behave like a normal software hasher.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopAccelerator;
+
+impl KeccakAccelerator for NoopAccelerator {
+    fn should_fall_back(&self, _buffered_len: usize, _threshold: usize) -> bool {
+        true
+    }
+
+    fn accelerated_digest(&self, _raw_data: &[u8], _delim: u8, _output: &mut [u8]) -> bool {
+        false
+    }
+}
+
+/// Offloads to risc0's `keccak_digest` guest precompile.
+#[cfg(feature = "risc0")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Risc0Accelerator;
+
+#[cfg(feature = "risc0")]
+impl KeccakAccelerator for Risc0Accelerator {
+    fn accelerated_digest(&self, raw_data: &[u8], delim: u8, output: &mut [u8]) -> bool {
+        // The precompile only produces a 256-bit digest; v224/v384/v512
+        // callers fall back to the incremental sponge instead.
+        if output.len() != 32 {
+            return false;
+        }
+        match risc0_zkvm::guest::env::keccak_digest(raw_data, delim) {
+            Ok(mut digest) => {
+                output.clone_from_slice(digest.as_mut_slice());
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Batch hashing of many independent messages, e.g. for Merkle-tree leaves/nodes.
+//!
+//! A loop of individual `Keccak::v256()`/`update`/`finalize` calls works but
+//! can't express "hash these N independent messages" as a single operation,
+//! so callers can't amortize setup or hand the work to a thread pool. This
+//! module provides that as a dedicated entry point, with the serial loop as
+//! the fallback when the `rayon` feature is off.
+use super::{Hasher, Keccak};
+
+/// Hashes each element of `inputs` with [`Keccak::v256`], writing digest `i`
+/// into `out[i]`.
+///
+/// # Panics
+///
+/// Panics if `out.len() != inputs.len()`.
+///
+/// [`Keccak::v256`]: struct.Keccak.html#method.v256
+pub fn hash_batch256(inputs: &[&[u8]], out: &mut [[u8; 32]]) {
+    assert_eq!(inputs.len(), out.len(), "inputs and out must have the same length");
+    hash_pairs(inputs.iter().zip(out.iter_mut()));
+}
+
+#[cfg(not(feature = "rayon"))]
+fn hash_pairs<'a>(pairs: impl Iterator<Item = (&'a &'a [u8], &'a mut [u8; 32])>) {
+    for (input, output) in pairs {
+        let mut hasher = Keccak::v256();
+        hasher.update(input);
+        hasher.finalize(output);
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn hash_pairs<'a>(pairs: impl Iterator<Item = (&'a &'a [u8], &'a mut [u8; 32])>) {
+    extern crate alloc;
+    use alloc::vec::Vec;
+    use rayon::prelude::*;
+
+    let pairs: Vec<_> = pairs.collect();
+    pairs.into_par_iter().for_each(|(input, output)| {
+        let mut hasher = Keccak::v256();
+        hasher.update(input);
+        hasher.finalize(output);
+    });
+}
@@ -0,0 +1,52 @@
+//! An adapter implementing `core::hash::Hasher`/`BuildHasher` on top of [`Keccak`], enabled
+//! with the `core-hasher` feature.
+//!
+//! This makes tiny-keccak usable anywhere generic over the standard library's
+//! `Hasher` trait (`HashMap`, `HashSet`, ...), at the cost of squeezing a
+//! `u64` out of a cryptographic sponge on every `finish()` call, which is
+//! far slower than the default SipHash. Unlike `RandomState`, it is unkeyed:
+//! every hasher starts from the same Keccak-256 state, so a given input
+//! always hashes to the same `u64`.
+use core::hash::{BuildHasher, Hasher as CoreHasher};
+
+use super::{Hasher, Keccak};
+
+/// Wraps [`Keccak`] so it can be used as a `core::hash::Hasher`.
+///
+/// Every `write` call feeds its bytes into the underlying sponge via
+/// [`Hasher::update`]; `finish` clones the accumulated state and squeezes 8
+/// bytes out of it, since `core::hash::Hasher::finish` takes `&self` while
+/// [`Hasher::finalize`] consumes the hasher.
+#[derive(Clone)]
+pub struct KeccakCoreHasher(Keccak);
+
+impl Default for KeccakCoreHasher {
+    fn default() -> Self {
+        KeccakCoreHasher(Keccak::v256())
+    }
+}
+
+impl CoreHasher for KeccakCoreHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        Hasher::update(&mut self.0, bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut output = [0u8; 8];
+        self.0.clone().finalize(&mut output);
+        u64::from_le_bytes(output)
+    }
+}
+
+/// A `BuildHasher` that produces [`KeccakCoreHasher`]s, for use as a
+/// `HashMap`/`HashSet` hasher factory.
+#[derive(Clone, Copy, Default)]
+pub struct KeccakBuildHasher;
+
+impl BuildHasher for KeccakBuildHasher {
+    type Hasher = KeccakCoreHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        KeccakCoreHasher::default()
+    }
+}
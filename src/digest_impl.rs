@@ -0,0 +1,69 @@
+//! Optional [`digest`](https://docs.rs/digest) trait impls, enabled with the `digest` feature.
+//!
+//! These let [`Keccak`] drop into `hmac`, `hkdf`, and any other code that is
+//! generic over `digest::Digest` instead of the native [`Hasher`] trait, at the
+//! cost of an extra dependency and a clone on every `finalize_into_reset` call
+//! (`FixedOutputReset` takes `&mut self`, while our [`Hasher::finalize`]
+//! consumes `self`).
+use super::{Hasher, Keccak};
+use digest::generic_array::typenum::{U104, U136, U144, U28, U32, U48, U64, U72};
+use digest::generic_array::GenericArray;
+use digest::{
+    BlockSizeUser, FixedOutput, FixedOutputReset, HashMarker, OutputSizeUser, Reset, Update,
+};
+
+macro_rules! impl_digest {
+    ($name:ident, $cons:ident, $size:ty, $block_size:ty) => {
+        /// Wraps [`Keccak`] so it can be driven through the `digest` crate's traits.
+        ///
+        /// [`Keccak`]: ../struct.Keccak.html
+        #[derive(Clone)]
+        pub struct $name(Keccak);
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name(Keccak::$cons())
+            }
+        }
+
+        impl HashMarker for $name {}
+
+        impl Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                Hasher::update(&mut self.0, data);
+            }
+        }
+
+        impl OutputSizeUser for $name {
+            type OutputSize = $size;
+        }
+
+        impl BlockSizeUser for $name {
+            type BlockSize = $block_size;
+        }
+
+        impl FixedOutput for $name {
+            fn finalize_into(self, out: &mut GenericArray<u8, Self::OutputSize>) {
+                Hasher::finalize(self.0, out.as_mut_slice());
+            }
+        }
+
+        impl FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut GenericArray<u8, Self::OutputSize>) {
+                Hasher::finalize(self.clone().0, out.as_mut_slice());
+                Reset::reset(self);
+            }
+        }
+
+        impl Reset for $name {
+            fn reset(&mut self) {
+                *self = Self::default();
+            }
+        }
+    };
+}
+
+impl_digest!(Keccak224, v224, U28, U144);
+impl_digest!(Keccak256, v256, U32, U136);
+impl_digest!(Keccak384, v384, U48, U104);
+impl_digest!(Keccak512, v512, U64, U72);
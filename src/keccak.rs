@@ -4,7 +4,15 @@ use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
 #[cfg(target_os = "zkvm")]
 extern crate alloc;
 #[cfg(target_os = "zkvm")]
-use alloc::{vec, vec::Vec};
+use crate::accelerator::{AcceleratedBuffer, KeccakAccelerator, NoopAccelerator, OffloadThreshold};
+#[cfg(all(target_os = "zkvm", feature = "risc0"))]
+use crate::accelerator::Risc0Accelerator;
+
+#[cfg(all(target_os = "zkvm", feature = "risc0"))]
+type DefaultAccelerator = Risc0Accelerator;
+#[cfg(all(target_os = "zkvm", not(feature = "risc0")))]
+type DefaultAccelerator = NoopAccelerator;
+
 /// The `Keccak` hash functions defined in [`Keccak SHA3 submission`].
 ///
 /// # Usage
@@ -15,15 +23,27 @@ use alloc::{vec, vec::Vec};
 /// ```
 ///
 /// [`Keccak SHA3 submission`]: https://keccak.team/files/Keccak-submission-3.pdf
+#[cfg(not(target_os = "zkvm"))]
 #[derive(Clone)]
 pub struct Keccak {
     state: KeccakState<KeccakF>,
-    #[cfg(target_os = "zkvm")]
-    raw_data: Vec<u8>,
-    #[cfg(target_os = "zkvm")]
-    slow_path: bool,
 }
 
+/// The `Keccak` hash functions defined in [`Keccak SHA3 submission`].
+///
+/// On `target_os = "zkvm"`, `Keccak` is generic over a [`KeccakAccelerator`]
+/// backend that it may offload small inputs to instead of absorbing them a
+/// round at a time; see [`Keccak::with_accelerator`].
+///
+/// [`Keccak SHA3 submission`]: https://keccak.team/files/Keccak-submission-3.pdf
+#[cfg(target_os = "zkvm")]
+#[derive(Clone)]
+pub struct Keccak<A: KeccakAccelerator = DefaultAccelerator> {
+    state: KeccakState<KeccakF>,
+    accel: AcceleratedBuffer<A>,
+}
+
+#[cfg(not(target_os = "zkvm"))]
 impl Keccak {
     const DELIM: u8 = 0x01;
 
@@ -58,14 +78,152 @@ impl Keccak {
     fn new(bits: usize) -> Keccak {
         Keccak {
             state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
-            #[cfg(target_os = "zkvm")]
-            raw_data: vec![],
-            #[cfg(target_os = "zkvm")]
-            slow_path: false,
         }
     }
 }
 
+#[cfg(target_os = "zkvm")]
+impl<A: KeccakAccelerator> Keccak<A> {
+    const DELIM: u8 = 0x01;
+
+    /// Creates a new [`Keccak`] hasher with a security level of 256 bits that
+    /// buffers input for `accelerator` until the accelerator's fall-back check
+    /// against `threshold` fires (by default, once more than `threshold` bytes
+    /// have been seen), instead of the default backend selected by the `risc0`
+    /// feature.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn with_accelerator(accelerator: A, threshold: OffloadThreshold) -> Keccak<A> {
+        Keccak {
+            state: KeccakState::new(bits_to_rate(256), Self::DELIM),
+            accel: AcceleratedBuffer::new(accelerator, threshold),
+        }
+    }
+}
+
+#[cfg(target_os = "zkvm")]
+impl Keccak<DefaultAccelerator> {
+    /// Creates  new [`Keccak`] hasher with a security level of 224 bits.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn v224() -> Self {
+        Keccak::new(224)
+    }
+
+    /// Creates  new [`Keccak`] hasher with a security level of 256 bits.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn v256() -> Self {
+        Keccak::new(256)
+    }
+
+    /// Creates  new [`Keccak`] hasher with a security level of 384 bits.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn v384() -> Self {
+        Keccak::new(384)
+    }
+
+    /// Creates  new [`Keccak`] hasher with a security level of 512 bits.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn v512() -> Self {
+        Keccak::new(512)
+    }
+
+    fn new(bits: usize) -> Self {
+        Keccak {
+            state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
+            accel: AcceleratedBuffer::new(DefaultAccelerator::default(), OffloadThreshold::default()),
+        }
+    }
+}
+
+/// The digest of the empty input (`&[]`) under [`keccak256`].
+///
+/// [`keccak256`]: fn.keccak256.html
+pub const KECCAK256_EMPTY: [u8; 32] = [
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+];
+
+/// The digest of the empty input (`&[]`) under [`keccak224`].
+///
+/// [`keccak224`]: fn.keccak224.html
+pub const KECCAK224_EMPTY: [u8; 28] = [
+    0xf7, 0x18, 0x37, 0x50, 0x2b, 0xa8, 0xe1, 0x08, 0x37, 0xbd, 0xd8, 0xd3, 0x65, 0xad, 0xb8, 0x55,
+    0x91, 0x89, 0x56, 0x02, 0xfc, 0x55, 0x2b, 0x48, 0xb7, 0x39, 0x0a, 0xbd,
+];
+
+/// The digest of the empty input (`&[]`) under [`keccak384`].
+///
+/// [`keccak384`]: fn.keccak384.html
+pub const KECCAK384_EMPTY: [u8; 48] = [
+    0x2c, 0x23, 0x14, 0x6a, 0x63, 0xa2, 0x9a, 0xcf, 0x99, 0xe7, 0x3b, 0x88, 0xf8, 0xc2, 0x4e, 0xaa,
+    0x7d, 0xc6, 0x0a, 0xa7, 0x71, 0x78, 0x0c, 0xcc, 0x00, 0x6a, 0xfb, 0xfa, 0x8f, 0xe2, 0x47, 0x9b,
+    0x2d, 0xd2, 0xb2, 0x13, 0x62, 0x33, 0x74, 0x41, 0xac, 0x12, 0xb5, 0x15, 0x91, 0x19, 0x57, 0xff,
+];
+
+/// The digest of the empty input (`&[]`) under [`keccak512`].
+///
+/// [`keccak512`]: fn.keccak512.html
+pub const KECCAK512_EMPTY: [u8; 64] = [
+    0x0e, 0xab, 0x42, 0xde, 0x4c, 0x3c, 0xeb, 0x92, 0x35, 0xfc, 0x91, 0xac, 0xff, 0xe7, 0x46, 0xb2,
+    0x9c, 0x29, 0xa8, 0xc3, 0x66, 0xb7, 0xc6, 0x0e, 0x4e, 0x67, 0xc4, 0x66, 0xf3, 0x6a, 0x43, 0x04,
+    0xc0, 0x0f, 0xa9, 0xca, 0xf9, 0xd8, 0x79, 0x76, 0xba, 0x46, 0x9b, 0xcb, 0xe0, 0x67, 0x13, 0xb4,
+    0x35, 0xf0, 0x91, 0xef, 0x27, 0x69, 0xfb, 0x16, 0x0c, 0xda, 0xb3, 0x3d, 0x36, 0x70, 0x68, 0x0e,
+];
+
+/// One-shot helper that hashes `data` with [`Keccak::v256`] and returns the digest.
+///
+/// Equivalent to constructing a [`Keccak`] hasher, feeding it `data` in a single
+/// `update` call and finalizing into a stack-allocated array, without requiring
+/// the caller to manage the hasher's lifetime themselves.
+///
+/// [`Keccak::v256`]: struct.Keccak.html#method.v256
+/// [`Keccak`]: struct.Keccak.html
+pub fn keccak256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(data.as_ref());
+    hasher.finalize(&mut output);
+    output
+}
+
+/// One-shot helper that hashes `data` with [`Keccak::v224`] and returns the digest.
+///
+/// [`Keccak::v224`]: struct.Keccak.html#method.v224
+pub fn keccak224(data: impl AsRef<[u8]>) -> [u8; 28] {
+    let mut output = [0u8; 28];
+    let mut hasher = Keccak::v224();
+    hasher.update(data.as_ref());
+    hasher.finalize(&mut output);
+    output
+}
+
+/// One-shot helper that hashes `data` with [`Keccak::v384`] and returns the digest.
+///
+/// [`Keccak::v384`]: struct.Keccak.html#method.v384
+pub fn keccak384(data: impl AsRef<[u8]>) -> [u8; 48] {
+    let mut output = [0u8; 48];
+    let mut hasher = Keccak::v384();
+    hasher.update(data.as_ref());
+    hasher.finalize(&mut output);
+    output
+}
+
+/// One-shot helper that hashes `data` with [`Keccak::v512`] and returns the digest.
+///
+/// [`Keccak::v512`]: struct.Keccak.html#method.v512
+pub fn keccak512(data: impl AsRef<[u8]>) -> [u8; 64] {
+    let mut output = [0u8; 64];
+    let mut hasher = Keccak::v512();
+    hasher.update(data.as_ref());
+    hasher.finalize(&mut output);
+    output
+}
+
+#[cfg(not(target_os = "zkvm"))]
 impl Hasher for Keccak {
     /// Absorb additional input. Can be called multiple times.
     ///
@@ -80,28 +238,10 @@ impl Hasher for Keccak {
     /// keccak.update(b" world");
     /// # }
     /// ```
-    #[cfg(not(target_os = "zkvm"))]
     fn update(&mut self, input: &[u8]) {
         self.state.update(input);
     }
 
-    #[cfg(target_os = "zkvm")]
-    fn update(&mut self, input: &[u8]) {
-        if !self.slow_path {
-            if self.raw_data.len() + input.len() > 100_000 {
-                self.slow_path = true;
-                self.state.update(&self.raw_data);
-            }
-            else {
-                self.raw_data.extend_from_slice(input);
-            }
-        }
-
-        if self.slow_path {
-            self.state.update(input);
-        }
-    }
-
     /// Pad and squeeze the state to the output.
     ///
     /// # Example
@@ -116,17 +256,36 @@ impl Hasher for Keccak {
     /// # }
     /// #
     /// ```
-    #[cfg(not(target_os = "zkvm"))]
     fn finalize(self, output: &mut [u8]) {
         self.state.finalize(output);
     }
+}
 
-    #[cfg(target_os = "zkvm")]
+#[cfg(target_os = "zkvm")]
+impl<A: KeccakAccelerator> Hasher for Keccak<A> {
+    /// Absorb additional input. Can be called multiple times.
+    ///
+    /// Buffered below the accelerator's offload threshold, inputs are kept
+    /// raw so [`finalize`](Hasher::finalize) can hand them to the
+    /// accelerator whole; once the threshold is crossed, everything seen so
+    /// far (and every subsequent call) is absorbed into the incremental
+    /// sponge instead.
+    fn update(&mut self, input: &[u8]) {
+        if let Some(data) = self.accel.absorb(input) {
+            self.state.update(data);
+        }
+    }
+
+    /// Pad and squeeze the state to the output.
     fn finalize(self, output: &mut [u8]) {
-        if self.slow_path {
+        if self.accel.fell_back() {
             self.state.finalize(output);
-        } else {
-            output.clone_from_slice(&risc0_zkvm::guest::env::keccak_digest(&self.raw_data, Self::DELIM).unwrap().as_mut_slice());
+        } else if !self.accel.finalize(Self::DELIM, output) {
+            // The accelerator declined (or failed) to service this call; replay
+            // the buffered input through the incremental sponge as a fallback.
+            let mut state = self.state;
+            state.update(self.accel.raw_data());
+            state.finalize(output);
         }
     }
 }